@@ -15,6 +15,7 @@
 
 use crate::core::point::Point;
 
+use std::any::Any;
 use std::collections::HashMap;
 
 use piston_window::*;
@@ -55,6 +56,25 @@ pub const CALLBACK_BUTTON_DOWN: u32 = 8;
 /// ```CallbackTypes::ButtonCallback``` callback.
 pub const CALLBACK_BUTTON_UP_INSIDE: u32 = 9;
 
+/// This is an enumerated type that describes an event that was injected by a `Widget` rather
+/// than having come from the windowing system, such as a `TimerWidget` firing its timeout.
+#[derive(Clone)]
+pub enum CallbackEvent {
+    /// Fired by a `TimerWidget` when its timeout has been reached.  `fired_at_ms` is the
+    /// wall-clock time the timer fired, and `elapsed_ms` is the true time that passed since the
+    /// timer last (re-)armed, which may differ from the configured timeout when checks only
+    /// happen on screen refresh.
+    TimerTriggered {
+        widget_id: i32,
+        fired_at_ms: u64,
+        elapsed_ms: u64,
+    },
+
+    /// Fired by an `IdleWidget` on an event-loop pass where no input/window events were
+    /// dispatched.
+    Idle { widget_id: i32 },
+}
+
 /// Callback type that takes no input.
 pub type BlankCallback = Box<Fn() -> ()>;
 
@@ -76,6 +96,14 @@ pub type KeyCallback = Box<Fn(i32, Key, ButtonState) -> ()>;
 /// Callback type that accepts a widget ID and a button code.
 pub type ButtonCallback = Box<Fn(i32, Button) -> ()>;
 
+/// Callback type that accepts a widget ID, the wall-clock time a timer fired, and the true
+/// elapsed time in milliseconds since the timer last armed.
+pub type TimerCallback = Box<Fn(i32, u64, u64) -> ()>;
+
+/// Callback type that accepts a widget ID and a reference to a caller-supplied context
+/// payload, downcast by the handler to the concrete type it expects.
+pub type ContextCallback = Box<Fn(i32, &Any) -> ()>;
+
 /// This is an enumerated type that is used to store numerous variations of callbacks that can
 /// be used within the `Widget` system.  This is written such that the `CallbackTypes` enum
 /// can be added to/extended as necessary.
@@ -100,6 +128,18 @@ pub enum CallbackTypes {
 
     /// Callback that supplies its widget ID and a button code.
     ButtonCallback { callback: ButtonCallback },
+
+    /// Callback that supplies its widget ID, the time a timer fired, and its true elapsed time.
+    TimerCallback { callback: TimerCallback },
+
+    /// Callback that carries its own boxed `payload`, separate from the widget ID of whatever
+    /// triggers it, so the same closure can be reused across widgets while each registration
+    /// supplies differentiated data.  The handler downcasts `payload` to the concrete type it
+    /// expects.
+    ContextCallback {
+        payload: Box<Any>,
+        callback: ContextCallback,
+    },
 }
 
 /// This is the `CallbackStore` that is used to store a list of `CallbackTypes` that are
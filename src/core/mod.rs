@@ -0,0 +1,3 @@
+pub mod callbacks;
+pub mod point;
+pub mod timer_manager;
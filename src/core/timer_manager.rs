@@ -0,0 +1,178 @@
+// Timer Manager
+// Central scheduler for timers, decoupled from the screen refresh rate.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::core::callbacks::CallbackEvent;
+use crate::widget::timer_widget::{time_ms, TimerMode};
+
+/// A single scheduled timer, tracked by the `TimerManager`.
+struct TimerEntry {
+    widget_id: i32,
+    initiated: u64,
+
+    /// Milliseconds from `initiated` until this particular occurrence fires.  This can differ
+    /// from `period` for a single occurrence - e.g. a timer resumed from a pause fires its next
+    /// occurrence after whatever time remained, but still falls back to the full `period` for
+    /// every occurrence after that.
+    next_in: u64,
+
+    /// The full interval used to re-arm a `TimerMode::Repeated` timer once it fires.
+    period: u64,
+    mode: TimerMode,
+}
+
+impl TimerEntry {
+    /// Returns the absolute time, in milliseconds since the `UNIX_EPOCH`, at which this timer
+    /// is due to fire.
+    fn expiry(&self) -> u64 {
+        self.initiated + self.next_in
+    }
+}
+
+/// This is the `TimerManager`.  It owns every registered timer, kept sorted by expiry time, so
+/// that timers fire on schedule regardless of how often the screen refreshes.  Each call to
+/// `tick()` only has to examine the earliest-expiring timers to know whether any are due, so
+/// the manager stays cheap to poll even with many timers registered.
+///
+/// A single `TimerManager` is meant to be shared - typically as `Rc<RefCell<TimerManager>>` -
+/// across every `TimerWidget` in an application, with the event loop calling `tick()` once per
+/// pass to advance the schedule.  `TimerWidget` is a thin handle around an entry in this
+/// manager; it registers itself here rather than tracking its own expiry.  Because widgets may
+/// each poll the same shared manager from their own `draw()` call, `tick()` is safe to call more
+/// than once per pass: firing and re-arming only happens for timers that are actually due, and
+/// fired events are buffered in `pending` until each widget claims the one addressed to it via
+/// `take()`, so one widget polling the manager can never consume another widget's event.
+pub struct TimerManager {
+    timers: Vec<TimerEntry>,
+    pending: Vec<CallbackEvent>,
+}
+
+impl TimerManager {
+    /// Constructor, creates a new `TimerManager` with no timers registered.
+    pub fn new() -> Self {
+        Self {
+            timers: Vec::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Registers a timer with this manager for `widget_id`, due to fire after `timeout`
+    /// milliseconds from now, and - for `TimerMode::Repeated` - re-firing every `timeout`
+    /// milliseconds after that.  The timer is inserted in expiry order, so the earliest-expiring
+    /// timer is always at the front of the list.
+    pub fn register(&mut self, widget_id: i32, timeout: u64, mode: TimerMode) {
+        self.register_at(widget_id, timeout, timeout, mode, time_ms());
+    }
+
+    /// Registers a timer whose next occurrence fires after `next_in` milliseconds, but whose
+    /// full re-arm interval (used for every occurrence after that, when `mode` is
+    /// `TimerMode::Repeated`) is `period`.  Used by `TimerWidget::resume()` so that resuming a
+    /// paused timer fires once after the time that remained when it was paused, then falls back
+    /// to its normally configured period.
+    pub fn register_partial(&mut self, widget_id: i32, next_in: u64, period: u64, mode: TimerMode) {
+        self.register_at(widget_id, next_in, period, mode, time_ms());
+    }
+
+    /// Same as `register_partial`, but takes the initiation time explicitly instead of
+    /// re-reading the clock, so that re-arming a timer from within `tick()` uses the `now`
+    /// already read for this pass rather than a fresh, later timestamp.
+    fn register_at(
+        &mut self,
+        widget_id: i32,
+        next_in: u64,
+        period: u64,
+        mode: TimerMode,
+        initiated: u64,
+    ) {
+        let entry = TimerEntry {
+            widget_id,
+            initiated,
+            next_in,
+            period,
+            mode,
+        };
+
+        let pos = self
+            .timers
+            .iter()
+            .position(|existing| existing.expiry() > entry.expiry())
+            .unwrap_or_else(|| self.timers.len());
+
+        self.timers.insert(pos, entry);
+    }
+
+    /// Removes every timer registered for `widget_id`.  Any event already pending for
+    /// `widget_id` is left untouched - a timer that already fired before being unregistered
+    /// still delivers that one event.
+    pub fn unregister(&mut self, widget_id: i32) {
+        self.timers.retain(|entry| entry.widget_id != widget_id);
+    }
+
+    /// Returns the number of milliseconds remaining before the timer registered for
+    /// `widget_id` is due to fire, or `None` if no such timer is registered.
+    pub fn remaining(&self, widget_id: i32) -> Option<u64> {
+        let now = time_ms();
+
+        self.timers
+            .iter()
+            .find(|entry| entry.widget_id == widget_id)
+            .map(|entry| entry.expiry().saturating_sub(now))
+    }
+
+    /// Checks the earliest-expiring timers against the current time, firing and, for repeated
+    /// timers, re-registering any that are due.  Meant to be called once per event-loop pass;
+    /// fired events are appended to `pending` for widgets to claim with `take()`.
+    ///
+    /// Due timers are drained into `due` before any are re-registered, so a repeated timer
+    /// whose `timeout` is `0` (or otherwise already expired the moment it is re-armed) is only
+    /// ever fired once per call to `tick()`, rather than being popped and re-inserted in a tight
+    /// loop against the same `now`.
+    pub fn tick(&mut self) {
+        let now = time_ms();
+        let mut due = Vec::new();
+
+        while let Some(entry) = self.timers.first() {
+            if entry.expiry() > now {
+                break;
+            }
+
+            due.push(self.timers.remove(0));
+        }
+
+        for entry in due {
+            self.pending.push(CallbackEvent::TimerTriggered {
+                widget_id: entry.widget_id,
+                fired_at_ms: now,
+                elapsed_ms: now - entry.initiated,
+            });
+
+            if entry.mode == TimerMode::Repeated {
+                self.register_at(entry.widget_id, entry.period, entry.period, entry.mode, now);
+            }
+        }
+    }
+
+    /// Removes and returns the pending `TimerTriggered` event for `widget_id`, if any, leaving
+    /// events pending for other widgets untouched.  Widgets sharing a `TimerManager` should call
+    /// `tick()` then `take()` from their own `draw()`/`inject_event()` - `tick()` is a no-op
+    /// once nothing new is due, so it is safe for every widget to call it.
+    pub fn take(&mut self, widget_id: i32) -> Option<CallbackEvent> {
+        let pos = self.pending.iter().position(|event| match event {
+            CallbackEvent::TimerTriggered { widget_id: id, .. } => *id == widget_id,
+            _ => false,
+        })?;
+
+        Some(self.pending.remove(pos))
+    }
+}
@@ -0,0 +1,121 @@
+// Idle Widget
+// Widget that fires off a callback whenever an event-loop pass dispatches no events.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use piston_window::*;
+
+use crate::core::callbacks::CallbackEvent;
+use crate::core::point::*;
+use crate::widget::config::*;
+use crate::widget::widget::*;
+
+pub const CALLBACK_IDLE: u32 = 101;
+
+/// This is the `IdleWidget`.  It contains no base widget, it only contains a flag reporting
+/// whether the most recent event-loop pass dispatched any events.
+///
+/// Example usage:
+///
+/// ```ignore
+/// let mut idle = IdleWidget::new(42);
+///
+/// // Called by the event loop once per pass:
+/// idle.set_idle(true);
+///
+/// match idle.inject_event() {
+///     Some(CallbackEvent::Idle { widget_id }) => eprintln!("widget {} is idle", widget_id),
+///     _ => (),
+/// }
+/// ```
+pub struct IdleWidget {
+    config: Configurable,
+    widget_id: i32,
+    enabled: bool,
+    idle: bool,
+}
+
+/// Implementation of the constructor for the `IdleWidget`.  Idle widgets are not accessible on
+/// the screen, so they have an origin of 0x0 and width of 0x0.
+///
+/// The idle widget gives applications a hook for background work - incremental loading,
+/// smoothing, polling - that should run between user interactions, rather than on a fixed
+/// interval like a `TimerWidget`.  The event loop is responsible for calling `set_idle()` once
+/// per pass to report whether any events were dispatched during that pass.
+impl IdleWidget {
+    /// Constructor, creates a new `IdleWidget` struct for `widget_id`.  Upon instantiation, the
+    /// widget is enabled, and assumes the event loop is not yet idle.
+    pub fn new(widget_id: i32) -> Self {
+        Self {
+            config: Configurable::new(),
+            widget_id,
+            enabled: true,
+            idle: false,
+        }
+    }
+
+    /// Called by the event loop once per pass to report whether any input or window events
+    /// were dispatched during that pass.  Pass `true` when the pass was otherwise idle.
+    pub fn set_idle(&mut self, idle: bool) {
+        self.idle = idle;
+    }
+
+    /// Enables or disables the idle callback.  When disabled, the widget never injects a
+    /// `CallbackEvent::Idle`, regardless of what is reported through `set_idle`.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+/// Implementation of the `IdleWidget` object with the `Widget` traits implemented.
+impl Widget for IdleWidget {
+    fn config(&mut self) -> &mut Configurable {
+        &mut self.config
+    }
+
+    /// Idle widget is always invalidated, this way, idle state is checked on every
+    /// event-loop pass.
+    fn is_invalidated(&mut self) -> bool {
+        true
+    }
+
+    /// Origin is always set to X/Y at points 0x0.
+    fn get_origin(&mut self) -> Point {
+        make_origin_point()
+    }
+
+    /// Size is always unsized, as idle widgets are invisible.
+    fn get_size(&mut self) -> crate::core::point::Size {
+        make_unsized()
+    }
+
+    /// This function injects events, as an idle event only occurs when the loop is idle.
+    fn injects_events(&mut self) -> bool {
+        true
+    }
+
+    /// Returns a `CallbackEvent::Idle` when the last event-loop pass reported no dispatched
+    /// events and the widget is enabled.
+    fn inject_event(&mut self) -> Option<CallbackEvent> {
+        if self.enabled && self.idle {
+            Some(CallbackEvent::Idle {
+                widget_id: self.widget_id,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Does not draw anything - idle widgets are invisible.
+    fn draw(&mut self, _context: Context, _graphics: &mut G2d, _clip: &DrawState) {}
+}
@@ -0,0 +1,4 @@
+pub mod config;
+pub mod idle_widget;
+pub mod timer_widget;
+pub mod widget;
@@ -14,31 +14,53 @@
 // limitations under the License.
 
 use piston_window::*;
+use std::cell::RefCell;
+use std::rc::Rc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::core::callbacks::CallbackEvent;
 use crate::core::point::*;
+use crate::core::timer_manager::TimerManager;
 use crate::widget::config::*;
 use crate::widget::widget::*;
 
 pub const CALLBACK_TIMER: u32 = 100;
 
+/// Controls whether a `TimerWidget` keeps re-arming itself after it fires.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TimerMode {
+    /// The timer fires once, then disables itself.
+    SingleShot,
+
+    /// The timer fires repeatedly, re-arming itself every time it triggers.  This is the
+    /// default mode.
+    Repeated,
+}
+
 /// This is the `TimerWidget`.  It contains no base widget, it only contains a start and end
 /// time,
 ///
 /// Example usage:
 /// IN PROGRESS
+///
+/// The actual countdown is tracked by a shared `TimerManager` - typically one `Rc<RefCell<_>>`
+/// handed to every `TimerWidget` in the application, with the event loop calling `tick()` on it
+/// once per pass.  This widget is just a thin handle that carries its own settings (`timeout`,
+/// `mode`, `widget_id`) and asks the manager whether its own `widget_id` has fired.
 pub struct TimerWidget {
     config: Configurable,
+    widget_id: i32,
+    manager: Rc<RefCell<TimerManager>>,
     enabled: bool,
-    initiated: u64,
     timeout: u64,
+    mode: TimerMode,
+    paused_remaining: Option<u64>,
     event: Option<CallbackEvent>,
 }
 
 /// Helper function that returns the current time in milliseconds since the `UNIX_EPOCH`.  This
 /// function is the equivalent of a `System.currentTimeMillis()` in Java.
-fn time_ms() -> u64 {
+pub(crate) fn time_ms() -> u64 {
     let since_the_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
 
     (since_the_epoch.as_secs() * 1_000) + (since_the_epoch.subsec_nanos() / 1_000_000) as u64
@@ -52,41 +74,125 @@ fn time_ms() -> u64 {
 ///
 /// Here are a few limitations of the timer as it currently stands:
 ///
-/// - Timer cannot be paused; it is enabled or disabled, and the timer resets when enabled.
-/// - Timer is called when the screen refreshes, so slower FPS settings will affect the timer.
+/// - The widget only calls `tick()` from `draw()`, so unless the event loop also calls `tick()`
+///   on the shared `TimerManager`, a due timer fires on the next screen refresh of any widget
+///   sharing that manager.  `fired_at_ms` on the resulting event is the time of that check, and
+///   `elapsed_ms` is the real time since the timer last armed, which can exceed the `timeout`.
 impl TimerWidget {
-    /// Constructor, creates a new `TimerWidget` struct with an empty timeout function.
-    pub fn new() -> Self {
+    /// Constructor, creates a new `TimerWidget` handle registered with `manager`.  Share the
+    /// same `manager` across every `TimerWidget` in the application so that one shared
+    /// `TimerManager` schedules all of their timers.  The widget's id defaults to `0`; call
+    /// `set_widget_id()` to give it the real id it should report in its `TimerTriggered`
+    /// events, so handlers can tell which timer fired.
+    pub fn new(manager: Rc<RefCell<TimerManager>>) -> Self {
+        let widget_id = 0;
+        let timeout = 0;
+        let mode = TimerMode::Repeated;
+
+        manager.borrow_mut().register(widget_id, timeout, mode);
+
         Self {
             config: Configurable::new(),
+            widget_id,
+            manager,
             enabled: true,
-            initiated: time_ms(),
-            timeout: 0,
+            timeout,
+            mode,
+            paused_remaining: None,
             event: None,
         }
     }
 
-    // Called to check the time since initiation, and call the timeout function when a timer has
-    // been triggered.
+    /// Sets the widget id this timer reports in its `TimerTriggered` events, re-registering with
+    /// the manager under the new id.  Without this, every `TimerWidget` reports the default id
+    /// of `0`, making it impossible for a handler to tell which timer fired.
+    pub fn set_widget_id(&mut self, widget_id: i32) {
+        if widget_id == self.widget_id {
+            return;
+        }
+
+        self.manager.borrow_mut().unregister(self.widget_id);
+        self.widget_id = widget_id;
+
+        if self.enabled {
+            self.manager
+                .borrow_mut()
+                .register(self.widget_id, self.timeout, self.mode);
+        }
+    }
+
+    // Called to advance the shared manager's schedule and check whether it has fired this
+    // widget's timer, stashing the resulting event for `inject_event` to pick up.  Safe to call
+    // from every `TimerWidget` sharing the same manager: `TimerManager::tick()` only fires
+    // timers that are actually due, and `take()` only ever claims the event addressed to this
+    // widget's own id, leaving other widgets' pending events untouched.
     fn tick(&mut self) {
         if !self.enabled {
             return;
         }
 
-        let elapsed = time_ms() - self.initiated;
+        let mut manager = self.manager.borrow_mut();
+
+        manager.tick();
 
-        if elapsed > self.timeout {
-            self.initiated = time_ms();
-            self.event = Some(CallbackEvent::TimerTriggered { widget_id: 0 });
+        if let Some(fired) = manager.take(self.widget_id) {
+            if self.mode == TimerMode::SingleShot {
+                self.enabled = false;
+            }
+
+            self.event = Some(fired);
         }
     }
 
-    /// Enables or disables the timer.  When disabled, the timer will not initiate the callback
-    /// function.  When re-enabled, the initiation time resets, so the timer will reset back to
-    /// zero, effectively resetting the entire timer.
+    /// Enables or disables the timer.  When disabled, the timer is unregistered from the
+    /// manager and will not initiate the callback function.  When re-enabled, the timer is
+    /// registered again for the full `timeout`, so it resets back to zero rather than
+    /// continuing from wherever it left off - use `pause()`/`resume()` instead if the timer
+    /// should continue from where it was.
     pub fn set_enabled(&mut self, enabled: bool) {
+        self.manager.borrow_mut().unregister(self.widget_id);
+        self.paused_remaining = None;
         self.enabled = enabled;
-        self.initiated = time_ms();
+
+        if enabled {
+            self.manager
+                .borrow_mut()
+                .register(self.widget_id, self.timeout, self.mode);
+        }
+    }
+
+    /// Pauses the timer, suspending its countdown without losing the time that has already
+    /// elapsed.  Unlike `set_enabled(false)`, a paused timer resumes exactly where it left off
+    /// when `resume()` is called, rather than resetting back to zero.
+    pub fn pause(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        self.paused_remaining = self.manager.borrow().remaining(self.widget_id);
+        self.manager.borrow_mut().unregister(self.widget_id);
+        self.enabled = false;
+    }
+
+    /// Resumes a timer previously suspended with `pause()`, continuing the countdown from the
+    /// elapsed time that had accumulated before it was paused.  A no-op if the timer is not
+    /// currently paused, so a stray or repeated call to `resume()` can never register a
+    /// duplicate entry for this widget's id.
+    ///
+    /// Only the next occurrence fires after the remaining time; once it fires, a
+    /// `TimerMode::Repeated` timer re-arms using the full `timeout`, not the shorter remaining
+    /// duration it resumed with.
+    pub fn resume(&mut self) {
+        let remaining = match self.paused_remaining.take() {
+            Some(remaining) => remaining,
+            None => return,
+        };
+
+        self.manager.borrow_mut().unregister(self.widget_id);
+        self.manager
+            .borrow_mut()
+            .register_partial(self.widget_id, remaining, self.timeout, self.mode);
+        self.enabled = true;
     }
 
     /// Sets the timeout in milliseconds for this timer.  Will trigger a call to the function
@@ -94,6 +200,29 @@ impl TimerWidget {
     /// timer is disabled by using `self.set_enabled(false)`.
     pub fn set_timeout(&mut self, timeout: u64) {
         self.timeout = timeout;
+        self.paused_remaining = None;
+        self.manager.borrow_mut().unregister(self.widget_id);
+
+        if self.enabled {
+            self.manager
+                .borrow_mut()
+                .register(self.widget_id, timeout, self.mode);
+        }
+    }
+
+    /// Sets the mode for this timer.  In `TimerMode::SingleShot` mode, the timer fires its
+    /// callback exactly once, then disables itself automatically.  In `TimerMode::Repeated`
+    /// mode (the default), the timer keeps re-arming itself every time it fires.
+    pub fn set_mode(&mut self, mode: TimerMode) {
+        self.mode = mode;
+        self.paused_remaining = None;
+        self.manager.borrow_mut().unregister(self.widget_id);
+
+        if self.enabled {
+            self.manager
+                .borrow_mut()
+                .register(self.widget_id, self.timeout, mode);
+        }
     }
 }
 